@@ -0,0 +1,199 @@
+use crate::metrics::MetricStats;
+use crate::statistics::Statistics;
+use crate::test_suite::TestResults;
+
+/// Which metric a multi-way comparison pivots on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    Total,
+    Parse,
+    Layout,
+    Paint,
+    Memory,
+}
+
+impl MetricKind {
+    /// Parse a metric name as accepted on the command line.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "total" => Some(MetricKind::Total),
+            "parse" => Some(MetricKind::Parse),
+            "layout" => Some(MetricKind::Layout),
+            "paint" => Some(MetricKind::Paint),
+            "memory" => Some(MetricKind::Memory),
+            _ => None,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            MetricKind::Total => "total",
+            MetricKind::Parse => "parse",
+            MetricKind::Layout => "layout",
+            MetricKind::Paint => "paint",
+            MetricKind::Memory => "memory",
+        }
+    }
+
+    fn select<'a>(&self, stats: &'a Statistics) -> &'a MetricStats {
+        match self {
+            MetricKind::Total => &stats.total_time,
+            MetricKind::Parse => &stats.parse_time,
+            MetricKind::Layout => &stats.layout_time,
+            MetricKind::Paint => &stats.paint_time,
+            MetricKind::Memory => &stats.memory,
+        }
+    }
+}
+
+/// A critcmp-style comparison across several result sets.
+///
+/// Columns are result sets (e.g. one per git commit); rows are renderers. Each
+/// cell carries the mean of the pivot metric and its multiple of the fastest
+/// (smallest) value in the row.
+#[derive(Debug, Clone)]
+pub struct ComparisonTable {
+    pub metric: MetricKind,
+    pub columns: Vec<String>,
+    pub rows: Vec<ComparisonRow>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ComparisonRow {
+    pub renderer: String,
+    /// One cell per column; `None` when that result set lacks the renderer.
+    pub cells: Vec<Option<f64>>,
+}
+
+impl ComparisonTable {
+    /// Render the table as aligned text.
+    pub fn render(&self) -> String {
+        let mut out = format!("Comparison on `{}` (mean, xN vs fastest)\n", self.metric.label());
+
+        let name_width = self
+            .rows
+            .iter()
+            .map(|r| r.renderer.len())
+            .chain(std::iter::once("renderer".len()))
+            .max()
+            .unwrap_or(8);
+
+        let col_width = 18usize;
+        out.push_str(&format!("{:<width$}", "renderer", width = name_width));
+        for col in &self.columns {
+            out.push_str(&format!("  {:<cw$}", truncate(col, col_width), cw = col_width));
+        }
+        out.push('\n');
+
+        for row in &self.rows {
+            let fastest = row
+                .cells
+                .iter()
+                .filter_map(|c| *c)
+                .fold(f64::INFINITY, f64::min);
+
+            out.push_str(&format!("{:<width$}", row.renderer, width = name_width));
+            for cell in &row.cells {
+                let text = match cell {
+                    Some(v) if fastest.is_finite() && fastest > 0.0 => {
+                        format!("{:.3} ({:.2}x)", v, v / fastest)
+                    }
+                    Some(v) => format!("{:.3}", v),
+                    None => "-".to_string(),
+                };
+                out.push_str(&format!("  {:<cw$}", text, cw = col_width));
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+impl TestResults {
+    /// Build an N-way comparison of `self` against `others`, pivoting on `metric`.
+    pub fn tabulate(&self, others: &[&TestResults], metric: MetricKind) -> ComparisonTable {
+        let sets: Vec<&TestResults> = std::iter::once(self).chain(others.iter().copied()).collect();
+
+        let columns: Vec<String> = sets.iter().map(|s| s.git_commit.clone()).collect();
+
+        // Union of renderer names across all sets: each set contributes its
+        // names in alphabetical order, appended in set order, skipping any
+        // already seen.
+        let mut renderers: Vec<String> = Vec::new();
+        for set in &sets {
+            let mut names: Vec<&String> = set.renderers.keys().collect();
+            names.sort();
+            for name in names {
+                if !renderers.contains(name) {
+                    renderers.push(name.clone());
+                }
+            }
+        }
+
+        let rows = renderers
+            .into_iter()
+            .map(|renderer| {
+                let cells = sets
+                    .iter()
+                    .map(|set| set.renderers.get(&renderer).map(|s| metric.select(s).mean))
+                    .collect();
+                ComparisonRow { renderer, cells }
+            })
+            .collect();
+
+        ComparisonTable {
+            metric,
+            columns,
+            rows,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_multipliers_relative_to_fastest() {
+        // Row has 10.0 and 20.0; the fastest is 10.0, so the cells should read
+        // 1.00x and 2.00x respectively.
+        let table = ComparisonTable {
+            metric: MetricKind::Total,
+            columns: vec!["a".to_string(), "b".to_string()],
+            rows: vec![ComparisonRow {
+                renderer: "rustkit".to_string(),
+                cells: vec![Some(10.0), Some(20.0)],
+            }],
+        };
+
+        let rendered = table.render();
+        assert!(rendered.contains("10.000 (1.00x)"), "{rendered}");
+        assert!(rendered.contains("20.000 (2.00x)"), "{rendered}");
+    }
+
+    #[test]
+    fn test_render_missing_cell_is_dash() {
+        let table = ComparisonTable {
+            metric: MetricKind::Parse,
+            columns: vec!["a".to_string(), "b".to_string()],
+            rows: vec![ComparisonRow {
+                renderer: "webkit".to_string(),
+                cells: vec![Some(5.0), None],
+            }],
+        };
+
+        let rendered = table.render();
+        assert!(rendered.contains("5.000 (1.00x)"), "{rendered}");
+        assert!(rendered.contains('-'), "{rendered}");
+    }
+}
+
+/// Truncate a column label to fit the fixed column width.
+fn truncate(s: &str, width: usize) -> String {
+    if s.len() <= width {
+        s.to_string()
+    } else {
+        format!("{}…", &s[..width.saturating_sub(1)])
+    }
+}
@@ -0,0 +1,62 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::metrics::MetricStats;
+use crate::test_suite::TestResults;
+
+/// Write one CSV row per (renderer, metric), suitable for diffing or feeding
+/// into a dashboard. Baseline value and percent change are filled in for any
+/// (renderer, metric) pair that appears in the baseline comparison.
+pub fn write_csv(results: &TestResults, path: &Path) -> Result<()> {
+    // Index the baseline comparison by (renderer, metric) for quick lookup.
+    let mut baseline: HashMap<(String, String), (f64, f64)> = HashMap::new();
+    if let Some(comparison) = &results.baseline_comparison {
+        for reg in comparison.regressions.iter().chain(comparison.improvements.iter()) {
+            baseline.insert(
+                (reg.renderer.clone(), reg.metric.clone()),
+                (reg.baseline_value, reg.percent_change),
+            );
+        }
+    }
+
+    let mut out = String::from(
+        "renderer,metric,mean,median,p95,p99,iterations,baseline_value,percent_change\n",
+    );
+
+    let mut renderers: Vec<_> = results.renderers.iter().collect();
+    renderers.sort_by(|a, b| a.0.cmp(b.0));
+
+    for (renderer, stats) in renderers {
+        for (metric, s) in [
+            ("parse_time_ms", &stats.parse_time),
+            ("layout_time_ms", &stats.layout_time),
+            ("paint_time_ms", &stats.paint_time),
+            ("total_time_ms", &stats.total_time),
+            ("memory_mb", &stats.memory),
+        ] {
+            out.push_str(&row(renderer, metric, s, results.iterations, &baseline));
+        }
+    }
+
+    std::fs::write(path, out)
+        .with_context(|| format!("Failed to write CSV {}", path.display()))?;
+    Ok(())
+}
+
+fn row(
+    renderer: &str,
+    metric: &str,
+    s: &MetricStats,
+    iterations: usize,
+    baseline: &HashMap<(String, String), (f64, f64)>,
+) -> String {
+    let (base, change) = match baseline.get(&(renderer.to_string(), metric.to_string())) {
+        Some((b, c)) => (format!("{:.6}", b), format!("{:.4}", c)),
+        None => (String::new(), String::new()),
+    };
+    format!(
+        "{},{},{:.6},{:.6},{:.6},{:.6},{},{},{}\n",
+        renderer, metric, s.mean, s.median, s.p95, s.p99, iterations, base, change
+    )
+}
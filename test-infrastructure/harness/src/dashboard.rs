@@ -0,0 +1,46 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::test_suite::TestResults;
+
+/// Payload POSTed to the dashboard: the full results plus the provenance a
+/// scheduled benchmark job wants to accumulate history against.
+#[derive(Debug, Serialize)]
+struct DashboardPayload<'a> {
+    git_commit: &'a str,
+    platform: &'a str,
+    timestamp: String,
+    reason: &'a str,
+    results: &'a TestResults,
+}
+
+/// Minimal response: the id of the record the dashboard created.
+#[derive(Debug, Deserialize)]
+struct DashboardResponse {
+    id: String,
+}
+
+/// POST `results` to the dashboard at `url`, returning the created record's id.
+pub fn report(results: &TestResults, url: &str, reason: &str) -> Result<String> {
+    let payload = DashboardPayload {
+        git_commit: &results.git_commit,
+        platform: &results.platform,
+        timestamp: results.timestamp.to_rfc3339(),
+        reason,
+        results,
+    };
+
+    let client = reqwest::blocking::Client::new();
+    let response = client
+        .post(url)
+        .json(&payload)
+        .send()
+        .with_context(|| format!("Failed to POST results to {}", url))?
+        .error_for_status()
+        .context("Dashboard rejected the report")?;
+
+    let body: DashboardResponse = response
+        .json()
+        .context("Failed to parse dashboard response")?;
+    Ok(body.id)
+}
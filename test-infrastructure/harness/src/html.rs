@@ -0,0 +1,167 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::metrics::MetricStats;
+use crate::statistics::Statistics;
+use crate::test_suite::TestResults;
+
+const SVG_WIDTH: f64 = 480.0;
+const SVG_HEIGHT: f64 = 160.0;
+const MARGIN: f64 = 24.0;
+
+/// Write a standalone HTML report summarizing a `TestResults`.
+///
+/// The report inlines everything it needs (CSS and SVG, no external JS) so it
+/// can be opened directly or attached to a CI artifact. Per renderer and per
+/// metric it draws a normal curve built from the summary mean and standard
+/// deviation, with the mean and its confidence interval (the individual
+/// iteration samples are not plotted), and — when a baseline was supplied — an annotated table of the
+/// detected regressions and improvements.
+pub fn write_report(results: &TestResults, path: &Path) -> Result<()> {
+    let mut body = String::new();
+
+    body.push_str(&format!(
+        "<h1>HiWave Performance Report</h1>\n\
+         <p class=\"meta\">platform <b>{}</b> &middot; commit <b>{}</b> &middot; \
+         {} iterations &middot; {:.2}s</p>\n",
+        results.platform, results.git_commit, results.iterations, results.total_duration_secs
+    ));
+
+    let mut renderers: Vec<(&String, &Statistics)> = results.renderers.iter().collect();
+    renderers.sort_by(|a, b| a.0.cmp(b.0));
+
+    for (renderer, stats) in renderers {
+        body.push_str(&format!("<h2>{}</h2>\n<div class=\"grid\">\n", renderer));
+        for (label, unit, metric) in [
+            ("Parse", "ms", &stats.parse_time),
+            ("Layout", "ms", &stats.layout_time),
+            ("Paint", "ms", &stats.paint_time),
+            ("Total", "ms", &stats.total_time),
+            ("Memory", "MB", &stats.memory),
+        ] {
+            body.push_str(&metric_card(label, unit, metric));
+        }
+        body.push_str("</div>\n");
+    }
+
+    if let Some(comparison) = &results.baseline_comparison {
+        body.push_str(&format!(
+            "<h2>Baseline comparison ({})</h2>\n",
+            comparison.baseline_commit
+        ));
+        body.push_str(&change_table("Regressions", "regression", &comparison.regressions));
+        body.push_str(&change_table("Improvements", "improvement", &comparison.improvements));
+    }
+
+    let html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>HiWave Performance Report</title>\n<style>{}</style>\n</head>\n\
+         <body>\n{}</body>\n</html>\n",
+        STYLE, body
+    );
+
+    std::fs::write(path, html)
+        .with_context(|| format!("Failed to write HTML report {}", path.display()))?;
+    Ok(())
+}
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem;color:#222}\
+h1{font-size:1.4rem}h2{font-size:1.1rem;margin-top:1.5rem}\
+.meta{color:#666}.grid{display:flex;flex-wrap:wrap;gap:1rem}\
+.card{border:1px solid #ddd;border-radius:6px;padding:.5rem}\
+.card h3{margin:.2rem 0;font-size:.95rem}\
+table{border-collapse:collapse;margin-top:.5rem}\
+td,th{border:1px solid #ddd;padding:.3rem .6rem;text-align:right;font-size:.85rem}\
+th:first-child,td:first-child{text-align:left}\
+.regression{color:#b00020}.improvement{color:#046a38}";
+
+/// Render one metric as a card containing an SVG distribution sketch.
+fn metric_card(label: &str, unit: &str, stats: &MetricStats) -> String {
+    format!(
+        "<div class=\"card\"><h3>{label}</h3>\n{svg}\n\
+         <div class=\"meta\">mean {mean:.3}{unit} \
+         (CI {lo:.3}–{hi:.3}) &middot; median {median:.3}{unit}</div></div>\n",
+        label = label,
+        svg = distribution_svg(stats),
+        mean = stats.mean,
+        unit = unit,
+        lo = stats.mean_ci.0,
+        hi = stats.mean_ci.1,
+        median = stats.median,
+    )
+}
+
+/// Draw a single normal density curve with the summary mean and std_dev over
+/// the sample's min..max range, shading the confidence interval and marking
+/// the mean. This is not a histogram or KDE: `MetricStats` carries no
+/// individual samples.
+fn distribution_svg(stats: &MetricStats) -> String {
+    let lo = stats.min;
+    let hi = stats.max.max(stats.min + f64::EPSILON);
+    let span = (hi - lo).max(f64::EPSILON);
+    let x_of = |v: f64| MARGIN + (v - lo) / span * (SVG_WIDTH - 2.0 * MARGIN);
+
+    let sigma = if stats.std_dev > 0.0 { stats.std_dev } else { span / 6.0 };
+    let steps = 80;
+    let peak = 1.0 / (sigma * (2.0 * std::f64::consts::PI).sqrt());
+
+    let mut curve = String::new();
+    for i in 0..=steps {
+        let v = lo + span * (i as f64 / steps as f64);
+        let z = (v - stats.mean) / sigma;
+        let density = (-0.5 * z * z).exp() / (sigma * (2.0 * std::f64::consts::PI).sqrt());
+        let x = x_of(v);
+        let y = SVG_HEIGHT - MARGIN - density / peak * (SVG_HEIGHT - 2.0 * MARGIN);
+        curve.push_str(&format!("{}{:.1},{:.1}", if i == 0 { "M" } else { " L" }, x, y));
+    }
+
+    let ci_x0 = x_of(stats.mean_ci.0);
+    let ci_x1 = x_of(stats.mean_ci.1);
+    let mean_x = x_of(stats.mean);
+
+    format!(
+        "<svg width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" \
+         xmlns=\"http://www.w3.org/2000/svg\">\
+         <rect x=\"{cx0:.1}\" y=\"{top:.1}\" width=\"{cw:.1}\" height=\"{ch:.1}\" \
+         fill=\"#4f86c6\" fill-opacity=\"0.18\"/>\
+         <path d=\"{curve}\" fill=\"none\" stroke=\"#4f86c6\" stroke-width=\"1.5\"/>\
+         <line x1=\"{mx:.1}\" y1=\"{top:.1}\" x2=\"{mx:.1}\" y2=\"{bot:.1}\" \
+         stroke=\"#b00020\" stroke-width=\"1.5\"/></svg>",
+        w = SVG_WIDTH,
+        h = SVG_HEIGHT,
+        cx0 = ci_x0,
+        cw = (ci_x1 - ci_x0).max(1.0),
+        ch = SVG_HEIGHT - 2.0 * MARGIN,
+        top = MARGIN,
+        bot = SVG_HEIGHT - MARGIN,
+        curve = curve,
+        mx = mean_x,
+    )
+}
+
+/// Render a table of regression/improvement entries.
+fn change_table(title: &str, class: &str, entries: &[crate::statistics::Regression]) -> String {
+    if entries.is_empty() {
+        return format!("<p class=\"meta\">{}: none</p>\n", title);
+    }
+    let mut rows = String::new();
+    for e in entries {
+        rows.push_str(&format!(
+            "<tr class=\"{class}\"><td>{r}</td><td>{m}</td><td>{b:.3}</td>\
+             <td>{c:.3}</td><td>{p:+.2}%</td></tr>\n",
+            class = class,
+            r = e.renderer,
+            m = e.metric,
+            b = e.baseline_value,
+            c = e.current_value,
+            p = e.percent_change,
+        ));
+    }
+    format!(
+        "<h3>{title}</h3>\n<table>\n\
+         <tr><th>renderer</th><th>metric</th><th>baseline</th><th>current</th><th>change</th></tr>\n\
+         {rows}</table>\n",
+        title = title,
+        rows = rows,
+    )
+}
@@ -4,12 +4,21 @@ use std::path::PathBuf;
 use tracing::{info, warn};
 use tracing_subscriber::EnvFilter;
 
+mod comparison;
+mod csv;
+mod dashboard;
+mod html;
 mod metrics;
+mod profiler;
+mod reftest;
 mod renderers;
 mod statistics;
 mod test_suite;
+mod workload;
 
-use crate::test_suite::MonteCarloTest;
+use crate::comparison::MetricKind;
+use crate::renderers::{RenderMode, RendererType};
+use crate::test_suite::{ExternalReport, MonteCarloTest, TestResults};
 
 #[derive(Parser, Debug)]
 #[command(name = "hiwave-perf")]
@@ -35,9 +44,113 @@ struct Args {
     #[arg(short, long)]
     verbose: bool,
 
+    /// Run a named workload from the workloads directory (optional)
+    #[arg(long)]
+    workload: Option<String>,
+
+    /// Directory containing workload JSON files
+    #[arg(long, default_value = "workloads")]
+    workloads_dir: PathBuf,
+
+    /// Dashboard URL to POST results to (optional)
+    #[arg(long)]
+    dashboard_url: Option<String>,
+
+    /// Free-text reason recorded with a dashboard report (e.g. triggering PR)
+    #[arg(long, default_value = "")]
+    reason: String,
+
     /// Baseline comparison file (optional)
     #[arg(short, long)]
     baseline: Option<PathBuf>,
+
+    /// Write a standalone HTML report to this path (optional)
+    #[arg(long)]
+    html: Option<PathBuf>,
+
+    /// External benchmark report(s) to fold into the results (repeatable)
+    #[arg(long)]
+    external: Vec<PathBuf>,
+
+    /// Write a machine-readable CSV export to this path (optional)
+    #[arg(long)]
+    csv: Option<PathBuf>,
+
+    /// Exit non-zero when any regression exceeds this percentage (CI gate)
+    #[arg(long)]
+    fail_threshold: Option<f64>,
+
+    /// Profilers to attach, e.g. --profilers samply --profilers sys_monitor
+    #[arg(long)]
+    profilers: Vec<String>,
+
+    /// Fraction of iterations to profile (0.0-1.0)
+    #[arg(long, default_value_t = 0.05)]
+    profile_fraction: f64,
+
+    /// Directory for profiler artifacts
+    #[arg(long, default_value = "profiles")]
+    profile_dir: PathBuf,
+
+    /// Recompute reported statistics with severe outliers removed
+    #[arg(long)]
+    filter_outliers: bool,
+
+    /// RNG seed for a reproducible run (optional)
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Warm-up iterations to discard before measuring
+    #[arg(long, default_value_t = 0)]
+    warmup: usize,
+
+    /// Target coefficient of variation (%) for adaptive sampling; enables adaptive mode
+    #[arg(long)]
+    target_cv: Option<f64>,
+
+    /// Wall-clock budget in seconds for adaptive sampling
+    #[arg(long)]
+    max_time: Option<f64>,
+
+    /// Run for this many seconds instead of a fixed iteration count
+    #[arg(long)]
+    bench_length_seconds: Option<f64>,
+
+    /// Pace iterations to this target rate (ops/sec) in time-bounded mode
+    #[arg(long)]
+    operations_per_second: Option<f64>,
+
+    /// Tabulate several result JSON files (critcmp-style) and exit
+    #[arg(long)]
+    tabulate: Vec<PathBuf>,
+
+    /// Metric to pivot the tabular comparison on (total, parse, layout, paint, memory)
+    #[arg(long, default_value = "total")]
+    tabulate_metric: String,
+
+    /// Run the pixel reftest suite instead of the perf harness
+    #[arg(long)]
+    reftest: bool,
+
+    /// Rasterization backend for reftests (cpu, gpu)
+    #[arg(long, default_value = "cpu")]
+    render_mode: String,
+
+    /// Per-channel tolerance (0-255) for reftest pixel comparison
+    #[arg(long, default_value_t = 0)]
+    reftest_tolerance: u8,
+}
+
+/// Short git commit hash for keying artifacts, or "unknown" if unavailable.
+fn current_git_commit() -> String {
+    std::process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8(o.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
 }
 
 fn main() -> Result<()> {
@@ -60,26 +173,114 @@ fn main() -> Result<()> {
     info!("Output: {}", args.output.display());
     info!("Renderer: {}", args.renderer);
 
+    // Tabulate mode renders a multi-way comparison table and exits.
+    if !args.tabulate.is_empty() {
+        let metric = MetricKind::parse(&args.tabulate_metric).unwrap_or(MetricKind::Total);
+        let loaded: Vec<TestResults> = args
+            .tabulate
+            .iter()
+            .map(TestResults::load)
+            .collect::<Result<_>>()?;
+        let (first, rest) = loaded
+            .split_first()
+            .ok_or_else(|| anyhow::anyhow!("no result files to tabulate"))?;
+        let others: Vec<&TestResults> = rest.iter().collect();
+        let table = first.tabulate(&others, metric);
+        println!("{}", table.render());
+        return Ok(());
+    }
+
+    // Reftest mode verifies rendered output instead of measuring timing.
+    if args.reftest {
+        let renderer_type = match args.renderer.to_lowercase().as_str() {
+            "webkit" => RendererType::WebKit,
+            "blink" => RendererType::Blink,
+            "gecko" => RendererType::Gecko,
+            _ => RendererType::RustKit,
+        };
+        let mode = match args.render_mode.to_lowercase().as_str() {
+            "gpu" => RenderMode::Gpu,
+            _ => RenderMode::Cpu,
+        };
+
+        let results = reftest::run_reftests(
+            &args.pages_dir,
+            &renderer_type,
+            mode,
+            args.reftest_tolerance,
+        )?;
+
+        let failures = results.iter().filter(|r| !r.passed).count();
+        info!("Reftest complete: {}/{} passed", results.len() - failures, results.len());
+        if failures > 0 {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // Create test suite
     let mut test = MonteCarloTest::new(args.iterations, args.pages_dir.clone())?;
+    test.set_filter_outliers(args.filter_outliers);
+    test.set_seed(args.seed);
+    test.set_adaptive(
+        args.warmup,
+        args.target_cv,
+        args.max_time.map(std::time::Duration::from_secs_f64),
+    );
+    if let Some(seconds) = args.bench_length_seconds {
+        test.set_time_bounded(
+            std::time::Duration::from_secs_f64(seconds),
+            args.operations_per_second,
+        );
+    }
 
-    // Configure renderers based on CLI args
-    match args.renderer.to_lowercase().as_str() {
-        "rustkit" => test.enable_renderer("rustkit"),
-        "webkit" => test.enable_renderer("webkit"),
-        "blink" => test.enable_renderer("blink"),
-        "gecko" => test.enable_renderer("gecko"),
-        "all" => {
-            test.enable_renderer("rustkit");
-            // Note: Baseline renderers may not be available on all platforms
-            if cfg!(target_os = "macos") {
-                test.enable_renderer("webkit");
+    // Configure profilers, if any were requested.
+    if !args.profilers.is_empty() {
+        let kinds: Vec<_> = args
+            .profilers
+            .iter()
+            .filter_map(|p| match profiler::ProfilerKind::parse(p) {
+                Some(k) => Some(k),
+                None => {
+                    warn!("Unknown profiler '{}', ignoring", p);
+                    None
+                }
+            })
+            .collect();
+        let commit = current_git_commit();
+        test.set_profilers(profiler::ProfilerConfig::new(
+            kinds,
+            args.profile_fraction,
+            args.profile_dir.clone(),
+            commit,
+        ));
+    }
+
+    // A workload fully specifies the run (renderers, budget, conditions);
+    // otherwise fall back to the individual CLI flags.
+    if let Some(workload_name) = &args.workload {
+        info!("Running workload '{}'", workload_name);
+        let workload = workload::Workload::load_by_name(&args.workloads_dir, workload_name)?;
+        workload.apply(&mut test)?;
+    } else {
+        // Configure renderers based on CLI args
+        match args.renderer.to_lowercase().as_str() {
+            "rustkit" => test.enable_renderer("rustkit"),
+            "webkit" => test.enable_renderer("webkit"),
+            "blink" => test.enable_renderer("blink"),
+            "gecko" => test.enable_renderer("gecko"),
+            "all" => {
+                test.enable_renderer("rustkit");
+                // Note: Baseline renderers may not be available on all platforms
+                if cfg!(target_os = "macos") {
+                    test.enable_renderer("webkit");
+                }
+                // Add other baseline renderers as they become available
+            }
+            other => {
+                warn!("Unknown renderer '{}', defaulting to rustkit only", other);
+                test.enable_renderer("rustkit");
             }
-            // Add other baseline renderers as they become available
-        }
-        other => {
-            warn!("Unknown renderer '{}', defaulting to rustkit only", other);
-            test.enable_renderer("rustkit");
         }
     }
 
@@ -87,6 +288,13 @@ fn main() -> Result<()> {
     info!("Running Monte Carlo performance tests...");
     let mut results = test.run()?;
 
+    // Fold in any externally-gathered reports before comparison/reporting.
+    for external_path in &args.external {
+        info!("Merging external report: {}", external_path.display());
+        let report = ExternalReport::load(external_path)?;
+        results.merge_external(report);
+    }
+
     // Compare against baseline if provided
     if let Some(baseline_path) = args.baseline {
         info!("Comparing against baseline: {}", baseline_path.display());
@@ -112,10 +320,48 @@ fn main() -> Result<()> {
     info!("Saving results to {}", args.output.display());
     results.save(&args.output)?;
 
+    // Write the HTML report if requested
+    if let Some(html_path) = &args.html {
+        info!("Writing HTML report to {}", html_path.display());
+        html::write_report(&results, html_path)?;
+    }
+
+    // Write the CSV export if requested
+    if let Some(csv_path) = &args.csv {
+        info!("Writing CSV export to {}", csv_path.display());
+        csv::write_csv(&results, csv_path)?;
+    }
+
+    // Report to the dashboard if configured.
+    if let Some(url) = &args.dashboard_url {
+        info!("Reporting results to dashboard: {}", url);
+        match dashboard::report(&results, url, &args.reason) {
+            Ok(id) => info!("Dashboard record created: {}", id),
+            Err(e) => warn!("Failed to report to dashboard: {}", e),
+        }
+    }
+
     // Print summary
     results.print_summary();
 
     info!("Performance testing complete!");
 
+    // CI gate: fail the process when a regression exceeds the threshold.
+    if let Some(threshold) = args.fail_threshold {
+        let breaching: Vec<_> = results
+            .regressions
+            .iter()
+            .filter(|r| r.percent_change > threshold)
+            .collect();
+        if !breaching.is_empty() {
+            warn!(
+                "{} regression(s) exceed the {:.2}% fail threshold",
+                breaching.len(),
+                threshold
+            );
+            std::process::exit(1);
+        }
+    }
+
     Ok(())
 }
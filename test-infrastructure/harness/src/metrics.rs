@@ -1,5 +1,12 @@
+use rand::prelude::*;
 use serde::{Deserialize, Serialize};
 
+/// Default number of bootstrap resamples used to estimate confidence intervals.
+pub const DEFAULT_BOOTSTRAP_RESAMPLES: usize = 100_000;
+
+/// Default confidence level for the reported intervals (95%).
+pub const DEFAULT_CONFIDENCE_LEVEL: f64 = 0.95;
+
 /// Performance metrics for a single render operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Metrics {
@@ -70,24 +77,138 @@ pub struct MetricStats {
     pub max: f64,
     pub p95: f64,
     pub p99: f64,
+    /// Confidence level used for the bootstrap intervals below (e.g. 0.95).
+    #[serde(default)]
+    pub confidence_level: f64,
+    /// 95% (or `confidence_level`) bootstrap CI for the mean: (lower, upper).
+    #[serde(default)]
+    pub mean_ci: (f64, f64),
+    /// Bootstrap CI for the median: (lower, upper).
+    #[serde(default)]
+    pub median_ci: (f64, f64),
+    /// Counts of Tukey-fence outliers in the sample this stat was computed from.
+    #[serde(default)]
+    pub outliers: OutlierCounts,
+}
+
+/// Classification of a single value relative to the Tukey fences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutlierClassification {
+    LowSevere,
+    LowMild,
+    None,
+    HighMild,
+    HighSevere,
+}
+
+/// Tally of outliers in a sample, bucketed by Tukey fence.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct OutlierCounts {
+    pub low_severe: usize,
+    pub low_mild: usize,
+    pub high_mild: usize,
+    pub high_severe: usize,
+}
+
+impl OutlierCounts {
+    /// Total number of values outside the mild fences.
+    pub fn total(&self) -> usize {
+        self.low_severe + self.low_mild + self.high_mild + self.high_severe
+    }
+
+    /// Number of values outside the severe fences.
+    pub fn severe(&self) -> usize {
+        self.low_severe + self.high_severe
+    }
 }
 
 impl MetricStats {
-    /// Compute statistics from a list of values
-    pub fn from_values(mut values: Vec<f64>) -> Self {
+    /// Compute statistics from a list of values, using the default bootstrap
+    /// settings for the confidence intervals.
+    pub fn from_values(values: Vec<f64>) -> Self {
+        Self::from_values_seeded(values, None)
+    }
+
+    /// Like [`Self::from_values`] but draws the bootstrap resamples from a
+    /// seeded RNG when `seed` is `Some`, so a `--seed` run reproduces the
+    /// reported confidence intervals exactly.
+    pub fn from_values_seeded(values: Vec<f64>, seed: Option<u64>) -> Self {
+        Self::from_values_with_confidence(values, DEFAULT_CONFIDENCE_LEVEL, seed)
+    }
+
+    /// Like [`Self::from_values`] but first removes severe Tukey outliers from
+    /// the sample, so the reported statistics reflect steady-state cost rather
+    /// than transient spikes. The `outliers` counts still describe the original
+    /// (unfiltered) sample.
+    pub fn from_values_filtered(values: Vec<f64>) -> Self {
+        Self::from_values_filtered_seeded(values, None)
+    }
+
+    /// Seeded counterpart to [`Self::from_values_filtered`]; see
+    /// [`Self::from_values_seeded`] for the reproducibility guarantee.
+    pub fn from_values_filtered_seeded(values: Vec<f64>, seed: Option<u64>) -> Self {
+        // The outlier classification below indexes into the sorted sample, so
+        // guard the empty case here too — otherwise `percentile_of_sorted`
+        // underflows before the guard in `from_values_with_confidence` runs.
+        if values.is_empty() {
+            return Self::from_values_with_confidence(values, DEFAULT_CONFIDENCE_LEVEL, seed);
+        }
+
+        let mut sorted = values.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let counts = classify_outliers(&sorted);
+        let fences = tukey_fences(&sorted);
+
+        let filtered: Vec<f64> = values
+            .into_iter()
+            .filter(|&v| v >= fences.low_severe && v <= fences.high_severe)
+            .collect();
+        let filtered = if filtered.is_empty() { sorted } else { filtered };
+
+        let mut stats = Self::from_values_with_confidence(filtered, DEFAULT_CONFIDENCE_LEVEL, seed);
+        stats.outliers = counts;
+        stats
+    }
+
+    /// Compute statistics from a list of values with an explicit confidence level.
+    ///
+    /// The mean and median intervals are estimated with a nonparametric
+    /// bootstrap: [`DEFAULT_BOOTSTRAP_RESAMPLES`] resamples of size `n` are
+    /// drawn with replacement, the statistic is computed on each, and the
+    /// `(1 ± confidence_level) / 2` percentiles of the resampled distribution
+    /// are taken as the interval bounds. When `seed` is `Some`, the resampling
+    /// RNG is seeded so the intervals are reproducible across runs.
+    pub fn from_values_with_confidence(
+        mut values: Vec<f64>,
+        confidence_level: f64,
+        seed: Option<u64>,
+    ) -> Self {
+        // Time-bounded runs produce variable (and occasionally empty) samples;
+        // return a zeroed stat rather than panicking on an empty vector.
+        if values.is_empty() {
+            return Self {
+                mean: 0.0,
+                median: 0.0,
+                std_dev: 0.0,
+                min: 0.0,
+                max: 0.0,
+                p95: 0.0,
+                p99: 0.0,
+                confidence_level,
+                mean_ci: (0.0, 0.0),
+                median_ci: (0.0, 0.0),
+                outliers: OutlierCounts::default(),
+            };
+        }
+
         values.sort_by(|a, b| a.partial_cmp(b).unwrap());
-        
+
         let count = values.len() as f64;
         let sum: f64 = values.iter().sum();
         let mean = sum / count;
-        
-        let median = if values.len() % 2 == 0 {
-            let mid = values.len() / 2;
-            (values[mid - 1] + values[mid]) / 2.0
-        } else {
-            values[values.len() / 2]
-        };
-        
+
+        let median = median_of_sorted(&values);
+
         let variance: f64 = values
             .iter()
             .map(|v| {
@@ -97,15 +218,20 @@ impl MetricStats {
             .sum::<f64>()
             / count;
         let std_dev = variance.sqrt();
-        
+
         let min = values[0];
         let max = values[values.len() - 1];
-        
+
         let p95_idx = ((values.len() as f64 * 0.95) as usize).min(values.len() - 1);
         let p99_idx = ((values.len() as f64 * 0.99) as usize).min(values.len() - 1);
         let p95 = values[p95_idx];
         let p99 = values[p99_idx];
-        
+
+        let (mean_ci, median_ci) =
+            bootstrap_intervals(&values, confidence_level, DEFAULT_BOOTSTRAP_RESAMPLES, seed);
+
+        let outliers = classify_outliers(&values);
+
         Self {
             mean,
             median,
@@ -114,6 +240,191 @@ impl MetricStats {
             max,
             p95,
             p99,
+            confidence_level,
+            mean_ci,
+            median_ci,
+            outliers,
         }
     }
 }
+
+/// The four Tukey fence boundaries derived from a sorted sample.
+struct Fences {
+    low_severe: f64,
+    low_mild: f64,
+    high_mild: f64,
+    high_severe: f64,
+}
+
+/// Compute the mild (1.5·IQR) and severe (3·IQR) Tukey fences for a sorted sample.
+fn tukey_fences(sorted: &[f64]) -> Fences {
+    let q1 = percentile_of_sorted(sorted, 0.25);
+    let q3 = percentile_of_sorted(sorted, 0.75);
+    let iqr = q3 - q1;
+    Fences {
+        low_severe: q1 - 3.0 * iqr,
+        low_mild: q1 - 1.5 * iqr,
+        high_mild: q3 + 1.5 * iqr,
+        high_severe: q3 + 3.0 * iqr,
+    }
+}
+
+/// Classify a value against the Tukey fences of its sample.
+fn classify_value(value: f64, fences: &Fences) -> OutlierClassification {
+    if value < fences.low_severe {
+        OutlierClassification::LowSevere
+    } else if value < fences.low_mild {
+        OutlierClassification::LowMild
+    } else if value > fences.high_severe {
+        OutlierClassification::HighSevere
+    } else if value > fences.high_mild {
+        OutlierClassification::HighMild
+    } else {
+        OutlierClassification::None
+    }
+}
+
+/// Bucket every value of a sorted sample into the Tukey outlier counts.
+fn classify_outliers(sorted: &[f64]) -> OutlierCounts {
+    let fences = tukey_fences(sorted);
+    let mut counts = OutlierCounts::default();
+    for &v in sorted {
+        match classify_value(v, &fences) {
+            OutlierClassification::LowSevere => counts.low_severe += 1,
+            OutlierClassification::LowMild => counts.low_mild += 1,
+            OutlierClassification::HighMild => counts.high_mild += 1,
+            OutlierClassification::HighSevere => counts.high_severe += 1,
+            OutlierClassification::None => {}
+        }
+    }
+    counts
+}
+
+/// Median of a non-empty slice, computed with linear-time selection.
+///
+/// The slice is partially reordered in place; callers that only need the
+/// median (such as the bootstrap resampler) avoid the `O(n log n)` of a full
+/// sort this way.
+fn median_by_selection(values: &mut [f64]) -> f64 {
+    let n = values.len();
+    let cmp = |a: &f64, b: &f64| a.partial_cmp(b).unwrap();
+    if n % 2 == 1 {
+        let (_, mid, _) = values.select_nth_unstable_by(n / 2, cmp);
+        *mid
+    } else {
+        let hi_idx = n / 2;
+        let (lo_half, hi, _) = values.select_nth_unstable_by(hi_idx, cmp);
+        let hi = *hi;
+        // After the partition everything in `lo_half` is <= `hi`; the lower
+        // central order statistic is simply its maximum.
+        let lo = lo_half.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        (lo + hi) / 2.0
+    }
+}
+
+/// Median of an already-sorted, non-empty slice.
+fn median_of_sorted(values: &[f64]) -> f64 {
+    if values.len() % 2 == 0 {
+        let mid = values.len() / 2;
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[values.len() / 2]
+    }
+}
+
+/// Draw `resamples` bootstrap samples of size `n` with replacement and return
+/// the `confidence_level` percentile intervals for the mean and median.
+fn bootstrap_intervals(
+    values: &[f64],
+    confidence_level: f64,
+    resamples: usize,
+    seed: Option<u64>,
+) -> ((f64, f64), (f64, f64)) {
+    let n = values.len();
+    // Seed the resampler when the run is seeded, so `mean_ci`/`median_ci` (and
+    // therefore the `ci_disjoint` regression verdict) replay exactly.
+    let mut rng = match seed {
+        Some(s) => StdRng::seed_from_u64(s),
+        None => StdRng::from_entropy(),
+    };
+
+    let mut means = Vec::with_capacity(resamples);
+    let mut medians = Vec::with_capacity(resamples);
+    let mut sample = vec![0.0; n];
+
+    for _ in 0..resamples {
+        let mut sum = 0.0;
+        for slot in sample.iter_mut() {
+            let v = values[rng.gen_range(0..n)];
+            *slot = v;
+            sum += v;
+        }
+        means.push(sum / n as f64);
+        // Use linear-time selection rather than a full sort: the median only
+        // needs one (odd n) or two (even n) order statistics, and this runs on
+        // every one of `resamples` iterations.
+        medians.push(median_by_selection(&mut sample));
+    }
+
+    means.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    medians.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let lower = (1.0 - confidence_level) / 2.0;
+    let upper = 1.0 - lower;
+    (
+        (percentile_of_sorted(&means, lower), percentile_of_sorted(&means, upper)),
+        (percentile_of_sorted(&medians, lower), percentile_of_sorted(&medians, upper)),
+    )
+}
+
+/// Value at the given quantile (0.0..=1.0) of an already-sorted, non-empty slice.
+fn percentile_of_sorted(sorted: &[f64], quantile: f64) -> f64 {
+    let idx = ((sorted.len() as f64 * quantile) as usize).min(sorted.len() - 1);
+    sorted[idx]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tukey_fences_plants_high_outlier() {
+        // A tight cluster with one large spike: the spike lands beyond the
+        // high severe fence, the cluster values stay inside the fences.
+        let mut sample: Vec<f64> = (0..20).map(|i| 10.0 + i as f64 * 0.1).collect();
+        sample.push(1000.0);
+        sample.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let fences = tukey_fences(&sample);
+        assert_eq!(classify_value(1000.0, &fences), OutlierClassification::HighSevere);
+        assert_eq!(classify_value(10.5, &fences), OutlierClassification::None);
+    }
+
+    #[test]
+    fn test_classify_outliers_buckets_spike() {
+        let mut sample: Vec<f64> = (0..20).map(|i| 10.0 + i as f64 * 0.1).collect();
+        sample.push(1000.0);
+        sample.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let counts = classify_outliers(&sample);
+        assert_eq!(counts.high_severe, 1);
+        assert_eq!(counts.total(), 1);
+        assert_eq!(counts.severe(), 1);
+    }
+
+    #[test]
+    fn test_seeded_bootstrap_is_reproducible() {
+        let values: Vec<f64> = (0..50).map(|i| 10.0 + (i % 7) as f64).collect();
+        let a = MetricStats::from_values_seeded(values.clone(), Some(42));
+        let b = MetricStats::from_values_seeded(values, Some(42));
+        assert_eq!(a.mean_ci, b.mean_ci);
+        assert_eq!(a.median_ci, b.median_ci);
+    }
+
+    #[test]
+    fn test_from_values_filtered_empty_does_not_panic() {
+        let stats = MetricStats::from_values_filtered(Vec::new());
+        assert_eq!(stats.mean, 0.0);
+        assert_eq!(stats.median, 0.0);
+    }
+}
@@ -0,0 +1,214 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::process::Child;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use tracing::{debug, warn};
+
+/// A profiler that can be attached to a subset of render iterations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfilerKind {
+    /// Sampling CPU profiler producing a flamegraph/trace (à la `samply`).
+    Samply,
+    /// Lightweight poller writing the process RSS as a time series; CPU usage
+    /// is not sampled.
+    SysMonitor,
+}
+
+impl ProfilerKind {
+    /// Parse a profiler name as accepted on the command line.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "samply" => Some(ProfilerKind::Samply),
+            "sys_monitor" | "sys-monitor" => Some(ProfilerKind::SysMonitor),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            ProfilerKind::Samply => "samply",
+            ProfilerKind::SysMonitor => "sys_monitor",
+        }
+    }
+}
+
+/// A profiling artifact emitted for one renderer during a run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileArtifact {
+    pub renderer: String,
+    pub profiler: String,
+    pub path: PathBuf,
+}
+
+/// Configuration for the profiler subsystem: which profilers to run, on what
+/// fraction of iterations, and where to write artifacts.
+///
+/// Profiling only a fraction of iterations keeps the overhead from perturbing
+/// the timing statistics that feed `MetricStats`.
+#[derive(Debug, Clone)]
+pub struct ProfilerConfig {
+    kinds: Vec<ProfilerKind>,
+    fraction: f64,
+    out_dir: PathBuf,
+    git_commit: String,
+}
+
+impl ProfilerConfig {
+    pub fn new(kinds: Vec<ProfilerKind>, fraction: f64, out_dir: PathBuf, git_commit: String) -> Self {
+        Self {
+            kinds,
+            fraction: fraction.clamp(0.0, 1.0),
+            out_dir,
+            git_commit,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty()
+    }
+
+    /// Whether the given iteration index should be profiled, given the fraction.
+    fn should_profile(&self, iteration: usize) -> bool {
+        if self.kinds.is_empty() || self.fraction <= 0.0 {
+            return false;
+        }
+        let stride = (1.0 / self.fraction).round().max(1.0) as usize;
+        iteration % stride == 0
+    }
+
+    /// Start profiling one render iteration, returning a session whose
+    /// [`Session::finish`] stops the profilers and returns their artifacts.
+    /// Dropping the session without calling `finish` does not stop them.
+    pub fn start(&self, renderer: &str, iteration: usize) -> Option<Session> {
+        if !self.should_profile(iteration) {
+            return None;
+        }
+
+        if let Err(e) = std::fs::create_dir_all(&self.out_dir) {
+            warn!("Could not create profiler output dir: {}", e);
+            return None;
+        }
+
+        let mut session = Session {
+            renderer: renderer.to_string(),
+            artifacts: Vec::new(),
+            samply: None,
+            sys_monitor: None,
+        };
+
+        for kind in &self.kinds {
+            let path = self.out_dir.join(format!(
+                "{}-{}-{}-{}.{}",
+                renderer,
+                self.git_commit,
+                kind.as_str(),
+                iteration,
+                match kind {
+                    ProfilerKind::Samply => "json",
+                    ProfilerKind::SysMonitor => "csv",
+                }
+            ));
+
+            let started = match kind {
+                ProfilerKind::Samply => session.start_samply(&path),
+                ProfilerKind::SysMonitor => session.start_sys_monitor(&path),
+            };
+
+            // Only advertise an artifact for a profiler that actually started,
+            // so `TestResults.profiles` lists no path for a profiler that
+            // failed to launch.
+            if started {
+                session.artifacts.push(ProfileArtifact {
+                    renderer: renderer.to_string(),
+                    profiler: kind.as_str().to_string(),
+                    path,
+                });
+            }
+        }
+
+        Some(session)
+    }
+}
+
+/// An active profiling session covering a single render iteration.
+pub struct Session {
+    renderer: String,
+    artifacts: Vec<ProfileArtifact>,
+    samply: Option<Child>,
+    sys_monitor: Option<(Arc<AtomicBool>, JoinHandle<()>)>,
+}
+
+impl Session {
+    /// Returns `true` if samply was spawned, so the caller knows whether to
+    /// expect an artifact.
+    fn start_samply(&mut self, path: &std::path::Path) -> bool {
+        // Best-effort: attach samply to the current process. If the tool isn't
+        // installed we simply record no flamegraph rather than failing the run.
+        let pid = std::process::id().to_string();
+        let child = std::process::Command::new("samply")
+            .args(["record", "--save-only", "-o"])
+            .arg(path)
+            .args(["-p", &pid])
+            .spawn();
+        match child {
+            Ok(child) => {
+                self.samply = Some(child);
+                true
+            }
+            Err(e) => {
+                debug!("samply unavailable ({}); skipping flamegraph", e);
+                false
+            }
+        }
+    }
+
+    fn start_sys_monitor(&mut self, path: &std::path::Path) -> bool {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_thread = stop.clone();
+        let path = path.to_path_buf();
+        let handle = std::thread::spawn(move || {
+            let mut series = String::from("elapsed_ms,rss_kb\n");
+            let start = std::time::Instant::now();
+            while !stop_thread.load(Ordering::Relaxed) {
+                if let Some(rss) = read_rss_kb() {
+                    series.push_str(&format!("{},{}\n", start.elapsed().as_millis(), rss));
+                }
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            }
+            let _ = std::fs::write(&path, series);
+        });
+        self.sys_monitor = Some((stop, handle));
+        true
+    }
+
+    /// Stop the profilers and return the artifacts they produced.
+    pub fn finish(mut self) -> Vec<ProfileArtifact> {
+        if let Some(mut child) = self.samply.take() {
+            // Ask samply to stop recording gracefully (SIGTERM) so it flushes
+            // the `--save-only` profile it was writing; SIGKILL would truncate
+            // or discard that artifact.
+            let _ = std::process::Command::new("kill")
+                .args(["-s", "TERM"])
+                .arg(child.id().to_string())
+                .status();
+            let _ = child.wait();
+        }
+        if let Some((stop, handle)) = self.sys_monitor.take() {
+            stop.store(true, Ordering::Relaxed);
+            let _ = handle.join();
+        }
+        debug!("Finished profiling {} ({} artifacts)", self.renderer, self.artifacts.len());
+        self.artifacts
+    }
+}
+
+/// Read the current process resident set size in kilobytes from `/proc`.
+fn read_rss_kb() -> Option<u64> {
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let rss_pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    // statm reports pages; assume the common 4 KiB page size.
+    Some(rss_pages * 4)
+}
@@ -0,0 +1,164 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+use crate::renderers::{RenderEngine, RenderMode, RendererType};
+
+/// Outcome of comparing a rendered page against its reference image.
+#[derive(Debug, Clone)]
+pub struct ReftestResult {
+    pub name: String,
+    pub passed: bool,
+    /// Number of pixels that differ beyond the tolerance.
+    pub differing_pixels: usize,
+    /// Largest per-channel difference observed.
+    pub max_difference: u8,
+    /// Path of the diff image written on failure, if any.
+    pub diff_path: Option<PathBuf>,
+}
+
+/// Run the reftest suite: for every `*.html` page in `pages_dir` that has a
+/// sibling `*.png` reference, render it and compare pixel-by-pixel.
+///
+/// The RustKit engine rasterizes the parsed document (see [`crate::renderers`]),
+/// so structurally different pages produce different framebuffers and this suite
+/// detects per-page rendering differences. The rasterizer is simplified — it
+/// paints one block per DOM node rather than running the full style → layout
+/// pipeline — so references must be generated from this harness, not a browser.
+pub fn run_reftests(
+    pages_dir: &Path,
+    renderer_type: &RendererType,
+    mode: RenderMode,
+    tolerance: u8,
+) -> Result<Vec<ReftestResult>> {
+    info!("Running reftests ({} backend)", mode);
+
+    let mut results = Vec::new();
+    for entry in std::fs::read_dir(pages_dir)
+        .with_context(|| format!("Failed to read pages dir {}", pages_dir.display()))?
+    {
+        let path = entry?.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("html") {
+            continue;
+        }
+
+        let reference = path.with_extension("png");
+        if !reference.exists() {
+            continue;
+        }
+
+        let name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let html = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        let result = compare_page(&name, &html, &reference, renderer_type, tolerance)?;
+        if result.passed {
+            info!("reftest {}: PASS", name);
+        } else {
+            warn!(
+                "reftest {}: FAIL ({} pixels differ, max {})",
+                name, result.differing_pixels, result.max_difference
+            );
+        }
+        results.push(result);
+    }
+
+    Ok(results)
+}
+
+/// Render a single page and diff it against `reference`.
+fn compare_page(
+    name: &str,
+    html: &str,
+    reference: &Path,
+    renderer_type: &RendererType,
+    tolerance: u8,
+) -> Result<ReftestResult> {
+    let ref_image = image::open(reference)
+        .with_context(|| format!("Failed to open reference {}", reference.display()))?
+        .to_rgba8();
+    let (width, height) = ref_image.dimensions();
+
+    let engine = RenderEngine::create(renderer_type)?;
+    engine.parse_html(html)?;
+    engine.layout(width, height)?;
+    let rendered = engine.render_to_buffer(width, height)?;
+
+    let mut differing_pixels = 0usize;
+    let mut max_difference = 0u8;
+    for (px, chunk) in ref_image.pixels().zip(rendered.chunks_exact(4)) {
+        let mut pixel_differs = false;
+        for channel in 0..4 {
+            let diff = px.0[channel].abs_diff(chunk[channel]);
+            max_difference = max_difference.max(diff);
+            if diff > tolerance {
+                pixel_differs = true;
+            }
+        }
+        if pixel_differs {
+            differing_pixels += 1;
+        }
+    }
+
+    let passed = differing_pixels == 0;
+    let diff_path = if passed {
+        None
+    } else {
+        let path = reference.with_file_name(format!("{}.diff.png", name));
+        write_diff_image(&ref_image, &rendered, width, height, tolerance, &path)?;
+        Some(path)
+    };
+
+    Ok(ReftestResult {
+        name: name.to_string(),
+        passed,
+        differing_pixels,
+        max_difference,
+        diff_path,
+    })
+}
+
+/// Write a side-by-side diff image: test | reference | highlighted delta.
+fn write_diff_image(
+    reference: &image::RgbaImage,
+    rendered: &[u8],
+    width: u32,
+    height: u32,
+    tolerance: u8,
+    path: &Path,
+) -> Result<()> {
+    let mut canvas = image::RgbaImage::new(width * 3, height);
+
+    for y in 0..height {
+        for x in 0..width {
+            let idx = ((y * width + x) * 4) as usize;
+            let test = image::Rgba([
+                rendered[idx],
+                rendered[idx + 1],
+                rendered[idx + 2],
+                rendered[idx + 3],
+            ]);
+            let refs = *reference.get_pixel(x, y);
+
+            let differs = (0..4).any(|c| test.0[c].abs_diff(refs.0[c]) > tolerance);
+            let delta = if differs {
+                image::Rgba([255, 0, 0, 255])
+            } else {
+                image::Rgba([0, 0, 0, 255])
+            };
+
+            canvas.put_pixel(x, y, test);
+            canvas.put_pixel(width + x, y, refs);
+            canvas.put_pixel(2 * width + x, y, delta);
+        }
+    }
+
+    canvas
+        .save(path)
+        .with_context(|| format!("Failed to write diff image {}", path.display()))?;
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use crate::metrics::{AggregatedMetrics, Metrics, MetricStats};
+use rand::prelude::*;
 use serde::{Deserialize, Serialize};
 
 /// Statistical analysis of performance metrics
@@ -14,18 +15,46 @@ pub struct Statistics {
 impl Statistics {
     /// Compute statistics from a list of metrics
     pub fn from_metrics(metrics: &[Metrics]) -> Self {
+        Self::from_metrics_opts(metrics, false)
+    }
+
+    /// Compute statistics from a list of metrics, optionally recomputing each
+    /// metric with its severe Tukey outliers removed.
+    pub fn from_metrics_opts(metrics: &[Metrics], filter_outliers: bool) -> Self {
+        Self::from_metrics_opts_seeded(metrics, filter_outliers, None)
+    }
+
+    /// Like [`Self::from_metrics_opts`] but seeds the bootstrap resampler so a
+    /// `--seed` run reproduces every metric's confidence intervals. A distinct
+    /// sub-seed is derived per metric, in a fixed order, so the intervals are
+    /// independent yet deterministic.
+    pub fn from_metrics_opts_seeded(
+        metrics: &[Metrics],
+        filter_outliers: bool,
+        seed: Option<u64>,
+    ) -> Self {
         let parse_times: Vec<f64> = metrics.iter().map(|m| m.parse_time_ms).collect();
         let layout_times: Vec<f64> = metrics.iter().map(|m| m.layout_time_ms).collect();
         let paint_times: Vec<f64> = metrics.iter().map(|m| m.paint_time_ms).collect();
         let total_times: Vec<f64> = metrics.iter().map(|m| m.total_time_ms).collect();
         let memory_values: Vec<f64> = metrics.iter().map(|m| m.memory_mb).collect();
 
+        let mut seeder = seed.map(StdRng::seed_from_u64);
+        let mut build = |values: Vec<f64>| {
+            let sub = seeder.as_mut().map(|r| r.gen::<u64>());
+            if filter_outliers {
+                MetricStats::from_values_filtered_seeded(values, sub)
+            } else {
+                MetricStats::from_values_seeded(values, sub)
+            }
+        };
+
         Self {
-            parse_time: MetricStats::from_values(parse_times),
-            layout_time: MetricStats::from_values(layout_times),
-            paint_time: MetricStats::from_values(paint_times),
-            total_time: MetricStats::from_values(total_times),
-            memory: MetricStats::from_values(memory_values),
+            parse_time: build(parse_times),
+            layout_time: build(layout_times),
+            paint_time: build(paint_times),
+            total_time: build(total_times),
+            memory: build(memory_values),
         }
     }
 
@@ -46,6 +75,99 @@ impl Statistics {
         println!("  Memory:       mean={:.2}MB  median={:.2}MB  p95={:.2}MB  p99={:.2}MB",
                  self.memory.mean, self.memory.median,
                  self.memory.p95, self.memory.p99);
+
+        for (label, stats) in [
+            ("Parse", &self.parse_time),
+            ("Layout", &self.layout_time),
+            ("Paint", &self.paint_time),
+            ("Total", &self.total_time),
+            ("Memory", &self.memory),
+        ] {
+            let total = stats.outliers.total();
+            if total > 0 {
+                println!("  {} outliers: {} ({} severe)", label, total, stats.outliers.severe());
+            }
+        }
+    }
+
+    /// Name of the first metric whose outlier count exceeds 10% of `iterations`,
+    /// if any — used to warn that the reported means may be unstable.
+    pub fn outlier_heavy_metric(&self, iterations: usize) -> Option<&'static str> {
+        if iterations == 0 {
+            return None;
+        }
+        let threshold = iterations / 10;
+        for (label, stats) in [
+            ("parse", &self.parse_time),
+            ("layout", &self.layout_time),
+            ("paint", &self.paint_time),
+            ("total", &self.total_time),
+            ("memory", &self.memory),
+        ] {
+            if stats.outliers.total() > threshold {
+                return Some(label);
+            }
+        }
+        None
+    }
+}
+
+/// Ordinary-least-squares fit of render cost against input size.
+///
+/// Models `total_time_ms ≈ slope · size + intercept`, where `slope` is the
+/// marginal per-node (or per-byte) cost and `intercept` is the fixed overhead.
+/// `r_squared` reports how much of the timing variance the linear model
+/// explains.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThroughputModel {
+    pub slope: f64,
+    pub intercept: f64,
+    pub r_squared: f64,
+    pub samples: usize,
+}
+
+impl ThroughputModel {
+    /// Fit an OLS line to `(size, total_time_ms)` pairs.
+    ///
+    /// Returns `None` when there are fewer than two pairs or the sizes show no
+    /// variation (a degenerate fit).
+    pub fn fit(pairs: &[(f64, f64)]) -> Option<Self> {
+        let n = pairs.len();
+        if n < 2 {
+            return None;
+        }
+
+        let mean_x = pairs.iter().map(|(x, _)| x).sum::<f64>() / n as f64;
+        let mean_y = pairs.iter().map(|(_, y)| y).sum::<f64>() / n as f64;
+
+        let mut sxx = 0.0;
+        let mut sxy = 0.0;
+        for (x, y) in pairs {
+            sxx += (x - mean_x).powi(2);
+            sxy += (x - mean_x) * (y - mean_y);
+        }
+        if sxx == 0.0 {
+            return None;
+        }
+
+        let slope = sxy / sxx;
+        let intercept = mean_y - slope * mean_x;
+
+        let mut ss_res = 0.0;
+        let mut ss_tot = 0.0;
+        for (x, y) in pairs {
+            let predicted = slope * x + intercept;
+            ss_res += (y - predicted).powi(2);
+            ss_tot += (y - mean_y).powi(2);
+        }
+        let r_squared = if ss_tot == 0.0 { 1.0 } else { 1.0 - ss_res / ss_tot };
+
+        Some(Self {
+            slope,
+            intercept,
+            r_squared,
+            samples: n,
+        })
     }
 }
 
@@ -144,6 +266,25 @@ mod tests {
         assert!(stats.total_time.mean > 40.0 && stats.total_time.mean < 50.0);
     }
 
+    #[test]
+    fn test_throughput_model_recovers_known_line() {
+        // Points lie exactly on y = 2x + 3, so the fit should recover the
+        // slope, intercept, and a perfect R².
+        let pairs: Vec<(f64, f64)> = (1..=10).map(|x| (x as f64, 2.0 * x as f64 + 3.0)).collect();
+        let model = ThroughputModel::fit(&pairs).expect("fit should succeed");
+        assert!((model.slope - 2.0).abs() < 1e-9);
+        assert!((model.intercept - 3.0).abs() < 1e-9);
+        assert!((model.r_squared - 1.0).abs() < 1e-9);
+        assert_eq!(model.samples, 10);
+    }
+
+    #[test]
+    fn test_throughput_model_degenerate_inputs() {
+        // Fewer than two points, or no variation in x, is a degenerate fit.
+        assert!(ThroughputModel::fit(&[(1.0, 2.0)]).is_none());
+        assert!(ThroughputModel::fit(&[(1.0, 2.0), (1.0, 5.0)]).is_none());
+    }
+
     #[test]
     fn test_coefficient_of_variation() {
         let values = vec![10.0, 12.0, 11.0, 13.0, 10.5];
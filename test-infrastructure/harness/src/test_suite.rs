@@ -4,12 +4,15 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tracing::{debug, info};
 
 use crate::metrics::Metrics;
+use crate::profiler::{ProfileArtifact, ProfilerConfig};
 use crate::renderers::{RenderEngine, RendererType};
-use crate::statistics::{BaselineComparison, Regression, Statistics};
+use crate::statistics::{
+    coefficient_of_variation, BaselineComparison, Regression, Statistics, ThroughputModel,
+};
 
 /// Represents a test page with varying complexity
 #[derive(Debug, Clone)]
@@ -33,23 +36,29 @@ pub struct Viewport {
     pub height: u32,
 }
 
+/// Common viewport sizes exercised by the test grid.
+const VIEWPORTS: [(u32, u32); 8] = [
+    (320, 568),   // iPhone SE
+    (375, 667),   // iPhone 8
+    (414, 896),   // iPhone 11 Pro Max
+    (768, 1024),  // iPad Portrait
+    (1024, 768),  // iPad Landscape
+    (1280, 720),  // HD
+    (1920, 1080), // Full HD
+    (2560, 1440), // QHD
+];
+
 impl Viewport {
-    pub fn random() -> Self {
-        let mut rng = thread_rng();
-        // Common viewport sizes
-        let sizes = [
-            (320, 568),   // iPhone SE
-            (375, 667),   // iPhone 8
-            (414, 896),   // iPhone 11 Pro Max
-            (768, 1024),  // iPad Portrait
-            (1024, 768),  // iPad Landscape
-            (1280, 720),  // HD
-            (1920, 1080), // Full HD
-            (2560, 1440), // QHD
-        ];
-        let (width, height) = sizes[rng.gen_range(0..sizes.len())];
+    /// Pick a random viewport using the supplied RNG, so a seeded run is reproducible.
+    pub fn choose<R: Rng>(rng: &mut R) -> Self {
+        let (width, height) = VIEWPORTS[rng.gen_range(0..VIEWPORTS.len())];
         Self { width, height }
     }
+
+    /// All viewports, used to build the stratified sampling grid.
+    fn all() -> impl Iterator<Item = Viewport> {
+        VIEWPORTS.iter().map(|&(width, height)| Viewport { width, height })
+    }
 }
 
 /// Monte Carlo test runner
@@ -57,8 +66,20 @@ pub struct MonteCarloTest {
     iterations: usize,
     test_pages: Vec<TestPage>,
     enabled_renderers: Vec<RendererType>,
+    filter_outliers: bool,
+    warmup: usize,
+    target_cv: Option<f64>,
+    max_time: Option<Duration>,
+    profilers: Option<ProfilerConfig>,
+    seed: Option<u64>,
+    explicit_schedule: Option<Vec<(usize, Viewport)>>,
+    bench_length: Option<Duration>,
+    ops_per_second: Option<f64>,
 }
 
+/// Number of measured iterations drawn between convergence checks in adaptive mode.
+const ADAPTIVE_BATCH: usize = 50;
+
 impl MonteCarloTest {
     /// Create a new Monte Carlo test suite
     pub fn new(iterations: usize, pages_dir: PathBuf) -> Result<Self> {
@@ -74,9 +95,80 @@ impl MonteCarloTest {
             iterations,
             test_pages,
             enabled_renderers: Vec::new(),
+            filter_outliers: false,
+            warmup: 0,
+            target_cv: None,
+            max_time: None,
+            profilers: None,
+            seed: None,
+            explicit_schedule: None,
+            bench_length: None,
+            ops_per_second: None,
         })
     }
 
+    /// Run for a wall-clock `Duration` instead of a fixed iteration count,
+    /// optionally pacing iterations to `ops_per_second` so the renderer is
+    /// measured under steady load rather than back-to-back.
+    pub fn set_time_bounded(&mut self, bench_length: Duration, ops_per_second: Option<f64>) {
+        self.bench_length = Some(bench_length);
+        self.ops_per_second = ops_per_second;
+    }
+
+    /// Seed the RNG so a run can be replayed exactly for debugging a regression.
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+    }
+
+    /// Override the fixed iteration count (e.g. from a workload file).
+    pub fn set_iterations(&mut self, iterations: usize) {
+        self.iterations = iterations;
+    }
+
+    /// Drive the run from an explicit list of `(page name, viewport)` pairs.
+    ///
+    /// Page names are resolved against the loaded test pages; an unknown name
+    /// is an error. This sets the iteration count to the number of pairs.
+    pub fn set_explicit_pairs(&mut self, pairs: Vec<(String, Viewport)>) -> Result<()> {
+        let resolved = pairs
+            .into_iter()
+            .map(|(name, viewport)| {
+                let idx = self
+                    .test_pages
+                    .iter()
+                    .position(|p| p.name == name)
+                    .with_context(|| format!("Workload references unknown page '{}'", name))?;
+                Ok((idx, viewport))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.iterations = resolved.len();
+        self.explicit_schedule = Some(resolved);
+        Ok(())
+    }
+
+    /// Attach a profiler subsystem that records a fraction of the iterations.
+    pub fn set_profilers(&mut self, profilers: ProfilerConfig) {
+        if !profilers.is_empty() {
+            self.profilers = Some(profilers);
+        }
+    }
+
+    /// Recompute reported statistics with severe outliers removed.
+    pub fn set_filter_outliers(&mut self, filter: bool) {
+        self.filter_outliers = filter;
+    }
+
+    /// Configure adaptive sampling: a warm-up count (discarded), a target
+    /// coefficient of variation for `total_time_ms` to converge to, and an
+    /// optional wall-clock budget. When `target_cv` is `None` the run uses the
+    /// fixed iteration count supplied to [`Self::new`].
+    pub fn set_adaptive(&mut self, warmup: usize, target_cv: Option<f64>, max_time: Option<Duration>) {
+        self.warmup = warmup;
+        self.target_cv = target_cv;
+        self.max_time = max_time;
+    }
+
     /// Enable a specific renderer for testing
     pub fn enable_renderer(&mut self, renderer: &str) {
         let renderer_type = match renderer.to_lowercase().as_str() {
@@ -165,7 +257,23 @@ impl MonteCarloTest {
     /// Run the Monte Carlo test suite
     pub fn run(&self) -> Result<TestResults> {
         let start_time = Instant::now();
-        let mut rng = thread_rng();
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        // In fixed-count mode, precompute a stratified schedule so every
+        // (page, viewport) combination is exercised a balanced number of times;
+        // adaptive mode draws on demand since its length is not known up front.
+        let schedule = if let Some(explicit) = &self.explicit_schedule {
+            Some(explicit.clone())
+        } else if self.target_cv.is_none() && self.bench_length.is_none() {
+            Some(self.stratified_schedule(&mut rng))
+        } else {
+            // Time-bounded and adaptive runs have no fixed length, so conditions
+            // are drawn on demand from the seeded RNG instead.
+            None
+        };
         
         // Initialize results storage
         let mut renderer_results: HashMap<String, Vec<Metrics>> = HashMap::new();
@@ -173,52 +281,228 @@ impl MonteCarloTest {
             renderer_results.insert(renderer.to_string(), Vec::new());
         }
 
-        info!("Starting {} iterations...", self.iterations);
+        let mut iterations_per_page: HashMap<String, usize> = HashMap::new();
+        // (input size, total_time_ms) pairs per renderer for throughput fitting.
+        let mut throughput_pairs: HashMap<String, Vec<(f64, f64)>> = HashMap::new();
+        let mut profiles: Vec<ProfileArtifact> = Vec::new();
+
+        // Warm-up iterations stabilize caches before measurement begins; their
+        // results are discarded so they don't bias the reported statistics.
+        if self.warmup > 0 {
+            info!("Warming up for {} iterations...", self.warmup);
+            for _ in 0..self.warmup {
+                let page = &self.test_pages[rng.gen_range(0..self.test_pages.len())];
+                let viewport = Viewport::choose(&mut rng);
+                for renderer_type in &self.enabled_renderers {
+                    self.measure_render(renderer_type, page, viewport)?;
+                }
+            }
+        }
 
-        // Run Monte Carlo iterations
-        for i in 0..self.iterations {
-            if i % 100 == 0 && i > 0 {
-                info!("Progress: {}/{} iterations", i, self.iterations);
+        // The renderer whose total-time CV drives the adaptive stopping rule.
+        let primary = self.enabled_renderers.first().map(|r| r.to_string());
+
+        let mut completed = 0usize;
+        info!("Starting measured iterations...");
+
+        loop {
+            // Stop before indexing the schedule so a zero-length run (e.g.
+            // `--iterations 0`) or an exhausted schedule does not index out of
+            // bounds; adaptive/time-bounded runs carry no schedule and rely on
+            // `should_stop` instead.
+            if let Some(sched) = &schedule {
+                if completed >= sched.len() {
+                    break;
+                }
             }
 
-            // Randomize test conditions
-            let page = &self.test_pages[rng.gen_range(0..self.test_pages.len())];
-            let viewport = Viewport::random();
+            // Pick test conditions: from the stratified schedule in fixed mode,
+            // otherwise draw uniformly (but still from the seeded RNG).
+            let (page, viewport) = match &schedule {
+                Some(sched) => {
+                    let (page_idx, viewport) = sched[completed];
+                    (&self.test_pages[page_idx], viewport)
+                }
+                None => (
+                    &self.test_pages[rng.gen_range(0..self.test_pages.len())],
+                    Viewport::choose(&mut rng),
+                ),
+            };
 
-            debug!("Iteration {}: page={}, viewport={}x{}", 
-                   i, page.name, viewport.width, viewport.height);
+            debug!("Iteration {}: page={}, viewport={}x{}",
+                   completed, page.name, viewport.width, viewport.height);
 
             // Test each enabled renderer
+            let size = page.complexity.element_count as f64;
             for renderer_type in &self.enabled_renderers {
+                let session = self
+                    .profilers
+                    .as_ref()
+                    .and_then(|p| p.start(&renderer_type.to_string(), completed));
                 let metrics = self.measure_render(renderer_type, page, viewport)?;
+                if let Some(session) = session {
+                    profiles.extend(session.finish());
+                }
+                throughput_pairs
+                    .entry(renderer_type.to_string())
+                    .or_default()
+                    .push((size, metrics.total_time_ms));
                 renderer_results
                     .get_mut(&renderer_type.to_string())
                     .unwrap()
                     .push(metrics);
             }
+            *iterations_per_page.entry(page.name.clone()).or_insert(0) += 1;
+            completed += 1;
+
+            if completed % 100 == 0 {
+                info!("Progress: {} iterations", completed);
+            }
+
+            if self.should_stop(completed, start_time, primary.as_deref(), &renderer_results) {
+                break;
+            }
+
+            // Pace to the target rate so the renderer is measured under steady
+            // load rather than back-to-back.
+            if let Some(ops) = self.ops_per_second {
+                if ops > 0.0 {
+                    let target = Duration::from_secs_f64(completed as f64 / ops);
+                    if let Some(remaining) = target.checked_sub(start_time.elapsed()) {
+                        std::thread::sleep(remaining);
+                    }
+                }
+            }
         }
 
         let total_duration = start_time.elapsed();
+        let ops_per_sec = if total_duration.as_secs_f64() > 0.0 {
+            completed as f64 / total_duration.as_secs_f64()
+        } else {
+            0.0
+        };
+        info!(
+            "Completed {} measured iterations ({:.1} ops/sec)",
+            completed, ops_per_sec
+        );
 
         // Compute statistics for each renderer
         let mut statistics_map = HashMap::new();
         for (renderer, metrics_list) in renderer_results {
-            let stats = Statistics::from_metrics(&metrics_list);
+            let stats =
+                Statistics::from_metrics_opts_seeded(&metrics_list, self.filter_outliers, self.seed);
             statistics_map.insert(renderer, stats);
         }
 
+        // Fit a throughput model per renderer where the pages varied in size.
+        let mut throughput_map = HashMap::new();
+        for (renderer, pairs) in throughput_pairs {
+            if let Some(model) = ThroughputModel::fit(&pairs) {
+                throughput_map.insert(renderer, model);
+            }
+        }
+
         Ok(TestResults {
             platform: get_platform(),
             timestamp: chrono::Utc::now(),
             git_commit: get_git_commit().unwrap_or_else(|_| "unknown".to_string()),
-            iterations: self.iterations,
+            seed: self.seed,
+            iterations: completed,
+            ops_per_sec,
+            iterations_per_page,
             total_duration_secs: total_duration.as_secs_f64(),
             renderers: statistics_map,
+            throughput: throughput_map,
+            profiles,
             regressions: Vec::new(),
             baseline_comparison: None,
         })
     }
 
+    /// Build a stratified schedule of `(page_index, viewport)` pairs covering
+    /// the full `test_pages × VIEWPORTS` grid a balanced number of times, with
+    /// the remainder spread round-robin, then shuffled into a random order.
+    fn stratified_schedule<R: Rng>(&self, rng: &mut R) -> Vec<(usize, Viewport)> {
+        let grid: Vec<(usize, Viewport)> = self
+            .test_pages
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, _)| Viewport::all().map(move |vp| (idx, vp)))
+            .collect();
+
+        if grid.is_empty() {
+            return Vec::new();
+        }
+
+        let base = self.iterations / grid.len();
+        let remainder = self.iterations % grid.len();
+
+        let mut schedule = Vec::with_capacity(self.iterations);
+        for (i, cell) in grid.iter().enumerate() {
+            let count = base + usize::from(i < remainder);
+            for _ in 0..count {
+                schedule.push(*cell);
+            }
+        }
+
+        schedule.shuffle(rng);
+        schedule
+    }
+
+    /// Decide whether the sampling loop should terminate.
+    ///
+    /// In fixed mode (`target_cv` unset) this stops at the configured iteration
+    /// count. In adaptive mode it keeps sampling in batches until the
+    /// coefficient of variation of the primary renderer's `total_time_ms` drops
+    /// below the target, the wall-clock budget is exhausted, or the iteration
+    /// count is hit as a hard cap.
+    fn should_stop(
+        &self,
+        completed: usize,
+        start_time: Instant,
+        primary: Option<&str>,
+        results: &HashMap<String, Vec<Metrics>>,
+    ) -> bool {
+        // Time-bounded mode runs until the wall-clock budget elapses.
+        if let Some(budget) = self.bench_length {
+            return start_time.elapsed() >= budget;
+        }
+
+        let target_cv = match self.target_cv {
+            None => return completed >= self.iterations,
+            Some(cv) => cv,
+        };
+
+        if let Some(max_time) = self.max_time {
+            if start_time.elapsed() >= max_time {
+                return true;
+            }
+        }
+
+        // Hard iteration cap so an always-noisy page can't run forever.
+        if completed >= self.iterations {
+            return true;
+        }
+
+        // Only re-evaluate convergence on batch boundaries.
+        if completed % ADAPTIVE_BATCH != 0 {
+            return false;
+        }
+
+        if let Some(samples) = primary.and_then(|p| results.get(p)) {
+            if samples.len() >= 2 {
+                let totals: Vec<f64> = samples.iter().map(|m| m.total_time_ms).collect();
+                let cv = coefficient_of_variation(&totals);
+                debug!("Adaptive CV after {} iterations: {:.2}%", completed, cv);
+                if cv <= target_cv {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
     /// Measure rendering performance for a single page
     fn measure_render(
         &self,
@@ -258,15 +542,55 @@ impl MonteCarloTest {
     }
 }
 
+/// Metrics gathered by an out-of-process benchmarker (e.g. one driving a real
+/// shipping browser) rather than an in-process [`RenderEngine`].
+///
+/// This mirrors windsock's external-benchmark support: the numbers are folded
+/// into the same `renderers` map as native runs, so regression detection and
+/// reporting stay format-agnostic.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalReport {
+    /// The renderer label these metrics belong to (e.g. "chrome-stable").
+    pub renderer: String,
+    /// Raw per-iteration metrics; aggregated with [`Statistics::from_metrics`].
+    pub metrics: Vec<Metrics>,
+}
+
+impl ExternalReport {
+    /// Load an external report from a JSON file.
+    pub fn load(path: &PathBuf) -> Result<Self> {
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read external report {}", path.display()))?;
+        let report = serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse external report {}", path.display()))?;
+        Ok(report)
+    }
+}
+
 /// Test results containing all metrics and statistics
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TestResults {
     pub platform: String,
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub git_commit: String,
+    /// RNG seed used for the run, recorded so it can be replayed exactly.
+    #[serde(default)]
+    pub seed: Option<u64>,
     pub iterations: usize,
+    /// Effective iterations per second achieved over the run.
+    #[serde(default)]
+    pub ops_per_sec: f64,
+    /// Measured iterations actually run per page (adaptive mode varies this).
+    #[serde(default)]
+    pub iterations_per_page: HashMap<String, usize>,
     pub total_duration_secs: f64,
     pub renderers: HashMap<String, Statistics>,
+    /// Per-renderer OLS fit of render cost against input size.
+    #[serde(default)]
+    pub throughput: HashMap<String, ThroughputModel>,
+    /// Profiling artifacts (flamegraphs / traces) emitted during the run.
+    #[serde(default)]
+    pub profiles: Vec<ProfileArtifact>,
     pub regressions: Vec<Regression>,
     pub baseline_comparison: Option<BaselineComparison>,
 }
@@ -286,6 +610,21 @@ impl TestResults {
         Ok(results)
     }
 
+    /// Splice an externally-gathered report into the results.
+    ///
+    /// The external metrics are aggregated with [`Statistics::from_metrics`],
+    /// so the renderer participates in [`Self::print_summary`] and
+    /// [`Self::compare_with_baseline`] like a native one. Severe-outlier
+    /// filtering and the run seed are not applied to external metrics.
+    /// `measure_render` is never invoked for these renderers.
+    pub fn merge_external(&mut self, report: ExternalReport) {
+        if report.metrics.is_empty() {
+            return;
+        }
+        let stats = Statistics::from_metrics(&report.metrics);
+        self.renderers.insert(report.renderer, stats);
+    }
+
     /// Compare with baseline and detect regressions
     pub fn compare_with_baseline(&mut self, baseline_path: &PathBuf) -> Result<BaselineComparison> {
         let baseline = Self::load(baseline_path)?;
@@ -305,8 +644,10 @@ impl TestResults {
                 let base = baseline_stats.total_time.mean;
                 let change_pct = ((current - base) / base) * 100.0;
 
-                if change_pct > 5.0 {
-                    // Regression threshold: 5% slower
+                if change_pct > 5.0
+                    && ci_disjoint(current_stats.total_time.mean_ci, baseline_stats.total_time.mean_ci)
+                {
+                    // Regression threshold: 5% slower, confirmed by non-overlapping CIs
                     let regression = Regression {
                         renderer: renderer.clone(),
                         metric: "total_time_ms".to_string(),
@@ -323,8 +664,10 @@ impl TestResults {
                 let base_mem = baseline_stats.memory.mean;
                 let mem_change_pct = ((current_mem - base_mem) / base_mem) * 100.0;
 
-                if mem_change_pct > 15.0 {
-                    // Memory regression threshold: 15% increase
+                if mem_change_pct > 15.0
+                    && ci_disjoint(current_stats.memory.mean_ci, baseline_stats.memory.mean_ci)
+                {
+                    // Memory regression threshold: 15% increase, confirmed by non-overlapping CIs
                     let regression = Regression {
                         renderer: renderer.clone(),
                         metric: "memory_mb".to_string(),
@@ -336,6 +679,29 @@ impl TestResults {
                     self.regressions.push(regression);
                 }
             }
+
+            // Flag algorithmic-scaling regressions via the throughput slope,
+            // which a single fixed-page timing comparison would miss.
+            if let (Some(current_model), Some(base_model)) =
+                (self.throughput.get(renderer), baseline.throughput.get(renderer))
+            {
+                if base_model.slope > 0.0 {
+                    let slope_change = ((current_model.slope - base_model.slope)
+                        / base_model.slope)
+                        * 100.0;
+                    if slope_change > 5.0 {
+                        let regression = Regression {
+                            renderer: renderer.clone(),
+                            metric: "throughput_slope".to_string(),
+                            baseline_value: base_model.slope,
+                            current_value: current_model.slope,
+                            percent_change: slope_change,
+                        };
+                        comparison.regressions.push(regression.clone());
+                        self.regressions.push(regression);
+                    }
+                }
+            }
         }
 
         self.baseline_comparison = Some(comparison.clone());
@@ -349,7 +715,7 @@ impl TestResults {
         println!("{}", "=".repeat(80));
         println!("Platform: {}", self.platform);
         println!("Iterations: {}", self.iterations);
-        println!("Duration: {:.2}s", self.total_duration_secs);
+        println!("Duration: {:.2}s ({:.1} ops/sec)", self.total_duration_secs, self.ops_per_sec);
         println!("Git Commit: {}", self.git_commit);
         println!();
 
@@ -357,6 +723,21 @@ impl TestResults {
             println!("Renderer: {}", renderer);
             println!("{}", "-".repeat(80));
             stats.print();
+            if let Some(metric) = stats.outlier_heavy_metric(self.iterations) {
+                println!(
+                    "  ⚠️  sample is outlier-heavy ({}); means may be unstable",
+                    metric
+                );
+            }
+            println!();
+        }
+
+        if !self.profiles.is_empty() {
+            println!("Profiling artifacts:");
+            println!("{}", "-".repeat(80));
+            for artifact in &self.profiles {
+                println!("  {} ({}): {}", artifact.renderer, artifact.profiler, artifact.path.display());
+            }
             println!();
         }
 
@@ -373,6 +754,27 @@ impl TestResults {
     }
 }
 
+/// Returns true when two confidence intervals do not overlap, i.e. the
+/// observed difference is unlikely to be measurement noise.
+fn ci_disjoint(a: (f64, f64), b: (f64, f64)) -> bool {
+    a.0 > b.1 || a.1 < b.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ci_disjoint;
+
+    #[test]
+    fn test_ci_disjoint() {
+        // Non-overlapping intervals are disjoint regardless of order.
+        assert!(ci_disjoint((10.0, 12.0), (13.0, 15.0)));
+        assert!(ci_disjoint((13.0, 15.0), (10.0, 12.0)));
+        // Overlapping or touching intervals are not.
+        assert!(!ci_disjoint((10.0, 13.0), (12.0, 15.0)));
+        assert!(!ci_disjoint((10.0, 12.0), (12.0, 15.0)));
+    }
+}
+
 /// Get current platform
 fn get_platform() -> String {
     if cfg!(target_os = "windows") {
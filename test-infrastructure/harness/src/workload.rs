@@ -0,0 +1,92 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::test_suite::{MonteCarloTest, Viewport};
+
+/// A fully-specified, checked-in benchmark scenario.
+///
+/// A workload fixes everything a run needs — how long to sample, which
+/// renderers to exercise, and either an explicit list of (page, viewport)
+/// pairs or a seed for reproducible random selection — so CI can run a named
+/// scenario from `workloads/` and get comparable results every time.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Workload {
+    pub name: String,
+    /// Fixed iteration count (ignored when `pairs` is non-empty).
+    #[serde(default)]
+    pub iterations: Option<usize>,
+    /// Wall-clock budget in seconds for adaptive mode.
+    #[serde(default)]
+    pub max_time_secs: Option<f64>,
+    /// Target coefficient of variation (%) for adaptive mode.
+    #[serde(default)]
+    pub target_cv: Option<f64>,
+    /// Warm-up iterations to discard.
+    #[serde(default)]
+    pub warmup: usize,
+    /// RNG seed for reproducible random selection.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Renderers to enable.
+    pub renderers: Vec<String>,
+    /// Explicit (page, viewport) pairs; when present, these drive the run
+    /// deterministically instead of random/stratified selection.
+    #[serde(default)]
+    pub pairs: Vec<WorkloadPair>,
+}
+
+/// One explicit (page, viewport) condition in a workload.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkloadPair {
+    pub page: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Workload {
+    /// Load a workload by name from `dir/<name>.json`.
+    pub fn load_by_name(dir: &Path, name: &str) -> Result<Self> {
+        let path = dir.join(format!("{}.json", name));
+        let data = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read workload {}", path.display()))?;
+        let workload = serde_json::from_str(&data)
+            .with_context(|| format!("Failed to parse workload {}", path.display()))?;
+        Ok(workload)
+    }
+
+    /// Apply this workload's settings to a test runner.
+    pub fn apply(&self, test: &mut MonteCarloTest) -> Result<()> {
+        for renderer in &self.renderers {
+            test.enable_renderer(renderer);
+        }
+        test.set_seed(self.seed);
+        test.set_adaptive(
+            self.warmup,
+            self.target_cv,
+            self.max_time_secs.map(Duration::from_secs_f64),
+        );
+
+        if !self.pairs.is_empty() {
+            let pairs: Vec<(String, Viewport)> = self
+                .pairs
+                .iter()
+                .map(|p| {
+                    (
+                        p.page.clone(),
+                        Viewport {
+                            width: p.width,
+                            height: p.height,
+                        },
+                    )
+                })
+                .collect();
+            test.set_explicit_pairs(pairs)?;
+        } else if let Some(iterations) = self.iterations {
+            test.set_iterations(iterations);
+        }
+
+        Ok(())
+    }
+}